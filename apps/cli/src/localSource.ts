@@ -5,10 +5,14 @@ import {
   type Collection,
   type CollectionSource,
   type DiscoveryConfig,
+  ENV_EXTENDS_KEY,
   matchesEntryPattern,
   parseDiscoveryConfig,
+  parseEnvText,
   pathIncludedByConfig,
+  renderEnvText,
   type RequestFile,
+  resolveEnvironment,
   type Workspace,
 } from "@eshttp/core";
 
@@ -194,12 +198,25 @@ export class LocalFsCollectionSource implements CollectionSource {
     return readFile(request.uri, "utf8");
   }
 
+  // Resolves `_extends` like the desktop backend: the text is returned unchanged
+  // unless the environment extends another, in which case the merged values are
+  // returned as sorted `KEY="value"` lines.
   async readEnvironmentFile(scopeUri: string, envName: string): Promise<string | null> {
-    const envPath = join(scopeUri, `.env.${envName}`);
-    if (!(await exists(envPath))) {
-      return null;
+    const readText = async (name: string): Promise<string | null> => {
+      const envPath = join(scopeUri, `.env.${name}`);
+      if (!(await exists(envPath))) {
+        return null;
+      }
+
+      return readFile(envPath, "utf8");
+    };
+
+    const text = await readText(envName);
+    if (text === null || !(ENV_EXTENDS_KEY in parseEnvText(text))) {
+      return text;
     }
 
-    return readFile(envPath, "utf8");
+    const values = await resolveEnvironment(envName, readText);
+    return values ? renderEnvText(values) : null;
   }
 }
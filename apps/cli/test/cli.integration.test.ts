@@ -89,6 +89,35 @@ describe("CLI integration", () => {
     expect(logs.some((line) => line.includes("200 OK"))).toBeTrue();
   });
 
+  test("run resolves keys inherited through _extends", async () => {
+    const workspaceRoot = join(sandboxDir, ".eshttp", "workspaces", "team-api");
+    const collectionDir = join(workspaceRoot, "users");
+    const requestPath = join(collectionDir, "Get user.http");
+
+    await mkdir(collectionDir, { recursive: true });
+    await writeFile(requestPath, "GET {{BASE}}/users\nAuthorization: Bearer {{TOKEN}}");
+    await writeFile(join(workspaceRoot, ".env.base"), "BASE=https://api.example.com\nTOKEN=base");
+    await writeFile(join(workspaceRoot, ".env.dev"), "_extends=base\nTOKEN=dev");
+
+    let sentRequest: { url: string; headers: Record<string, string> } | undefined;
+    const transport: HttpTransport = {
+      send: async (request) => {
+        sentRequest = request;
+        return { status: 200, statusText: "OK", headers: {}, body: "{}" };
+      },
+    };
+
+    await runCli(["env", "dev"], { cwd: sandboxDir });
+    await runCli(["run", requestPath], {
+      cwd: sandboxDir,
+      source: new LocalFsCollectionSource(sandboxDir),
+      transport,
+    });
+
+    expect(sentRequest?.url).toBe("https://api.example.com/users");
+    expect(sentRequest?.headers.Authorization).toBe("Bearer dev");
+  });
+
   test("env command persists and reads active env", async () => {
     await runCli(["env", "dev"], { cwd: sandboxDir });
     await runCli(["env"], { cwd: sandboxDir });
@@ -0,0 +1,490 @@
+use crate::{AppError, GrpcCall, GrpcResponse};
+use base64::prelude::{Engine as _, BASE64_STANDARD};
+use prost::Message;
+use prost_reflect::{DescriptorPool, DynamicMessage, MessageDescriptor, MethodDescriptor};
+use std::collections::HashMap;
+use std::fs;
+use std::path::Path;
+use std::str::FromStr;
+use tonic::codec::{Codec, DecodeBuf, Decoder, EncodeBuf, Encoder};
+use tonic::codegen::http::uri::PathAndQuery;
+use tonic::metadata::MetadataMap;
+use tonic::transport::{ClientTlsConfig, Endpoint};
+use tonic::{Request, Status};
+
+/// Encodes and decodes `DynamicMessage`s so calls can be made from descriptors alone,
+/// without generated stubs.
+#[derive(Clone)]
+pub(crate) struct DynamicCodec {
+    decode_descriptor: MessageDescriptor,
+}
+
+impl DynamicCodec {
+    pub(crate) fn new(decode_descriptor: MessageDescriptor) -> Self {
+        Self { decode_descriptor }
+    }
+}
+
+pub(crate) struct DynamicEncoder;
+
+impl Encoder for DynamicEncoder {
+    type Item = DynamicMessage;
+    type Error = Status;
+
+    fn encode(&mut self, item: Self::Item, dst: &mut EncodeBuf<'_>) -> Result<(), Self::Error> {
+        item.encode(dst)
+            .map_err(|error| Status::internal(format!("Failed to encode message: {}", error)))
+    }
+}
+
+pub(crate) struct DynamicDecoder {
+    descriptor: MessageDescriptor,
+}
+
+impl Decoder for DynamicDecoder {
+    type Item = DynamicMessage;
+    type Error = Status;
+
+    fn decode(&mut self, src: &mut DecodeBuf<'_>) -> Result<Option<Self::Item>, Self::Error> {
+        DynamicMessage::decode(self.descriptor.clone(), src)
+            .map(Some)
+            .map_err(|error| Status::internal(format!("Failed to decode message: {}", error)))
+    }
+}
+
+impl Codec for DynamicCodec {
+    type Encode = DynamicMessage;
+    type Decode = DynamicMessage;
+    type Encoder = DynamicEncoder;
+    type Decoder = DynamicDecoder;
+
+    fn encoder(&mut self) -> Self::Encoder {
+        DynamicEncoder
+    }
+
+    fn decoder(&mut self) -> Self::Decoder {
+        DynamicDecoder {
+            descriptor: self.decode_descriptor.clone(),
+        }
+    }
+}
+
+/// Loads descriptors from a `.proto` source (compiled with protox, imports resolved
+/// relative to its directory) or from an encoded `FileDescriptorSet`.
+fn load_descriptor_pool(descriptor_path: &str) -> Result<DescriptorPool, AppError> {
+    let path = fs::canonicalize(descriptor_path).map_err(|error| {
+        AppError::Io(format!(
+            "Failed to resolve descriptor path {}: {}",
+            descriptor_path, error
+        ))
+    })?;
+
+    if path.extension().and_then(|extension| extension.to_str()) == Some("proto") {
+        let include = path.parent().unwrap_or_else(|| Path::new("."));
+        let file_name = path.file_name().ok_or_else(|| {
+            AppError::Validation(format!("Invalid proto path: {}", path.display()))
+        })?;
+        let descriptor_set = protox::compile([file_name], [include]).map_err(|error| {
+            AppError::Parse(format!("Failed to compile {}: {}", path.display(), error))
+        })?;
+        return DescriptorPool::from_file_descriptor_set(descriptor_set).map_err(|error| {
+            AppError::Parse(format!(
+                "Invalid descriptors in {}: {}",
+                path.display(),
+                error
+            ))
+        });
+    }
+
+    let bytes = fs::read(&path)
+        .map_err(|error| AppError::Io(format!("Failed to read {}: {}", path.display(), error)))?;
+    DescriptorPool::decode(bytes.as_slice()).map_err(|error| {
+        AppError::Parse(format!(
+            "Failed to decode descriptor set {}: {}",
+            path.display(),
+            error
+        ))
+    })
+}
+
+/// Accepts `package.Service/Method` as well as `package.Service.Method`.
+fn find_method(pool: &DescriptorPool, method: &str) -> Result<MethodDescriptor, AppError> {
+    let trimmed = method.trim().trim_start_matches('/');
+    let split = trimmed.rfind('/').or_else(|| trimmed.rfind('.'));
+    let Some(index) = split else {
+        return Err(AppError::Validation(format!(
+            "Invalid gRPC method '{}'. Expected: package.Service/Method",
+            method
+        )));
+    };
+
+    let (service_name, method_name) = (&trimmed[..index], &trimmed[index + 1..]);
+    let service = pool.get_service_by_name(service_name).ok_or_else(|| {
+        AppError::Validation(format!(
+            "Service not found in descriptors: {}",
+            service_name
+        ))
+    })?;
+    let descriptor = service
+        .methods()
+        .find(|candidate| candidate.name() == method_name)
+        .ok_or_else(|| {
+            AppError::Validation(format!(
+                "Method not found on {}: {}",
+                service_name, method_name
+            ))
+        })?;
+
+    if descriptor.is_client_streaming() || descriptor.is_server_streaming() {
+        return Err(AppError::Validation(format!(
+            "Method {} is streaming; only unary calls are supported",
+            descriptor.full_name()
+        )));
+    }
+
+    Ok(descriptor)
+}
+
+fn metadata_to_map(metadata: &MetadataMap) -> HashMap<String, String> {
+    let mut values = HashMap::new();
+    for (name, value) in metadata.clone().into_headers().iter() {
+        values.insert(
+            name.to_string(),
+            value.to_str().unwrap_or_default().to_string(),
+        );
+    }
+
+    values
+}
+
+/// Decodes a protobuf payload into JSON using a base64-encoded `FileDescriptorSet`.
+pub(crate) fn decode_message(
+    body_base64: &str,
+    descriptor_set_base64: &str,
+    message_type: &str,
+) -> Result<String, AppError> {
+    let body = BASE64_STANDARD
+        .decode(body_base64.trim())
+        .map_err(|error| AppError::Parse(format!("Invalid base64 body: {}", error)))?;
+    let descriptor_set = BASE64_STANDARD
+        .decode(descriptor_set_base64.trim())
+        .map_err(|error| AppError::Parse(format!("Invalid base64 descriptor set: {}", error)))?;
+
+    let pool = DescriptorPool::decode(descriptor_set.as_slice())
+        .map_err(|error| AppError::Parse(format!("Failed to decode descriptor set: {}", error)))?;
+    let descriptor = pool
+        .get_message_by_name(message_type.trim_start_matches('.'))
+        .ok_or_else(|| {
+            AppError::Validation(format!(
+                "Message type not found in descriptors: {}",
+                message_type
+            ))
+        })?;
+
+    let message = DynamicMessage::decode(descriptor, body.as_slice()).map_err(|error| {
+        AppError::Parse(format!(
+            "Failed to decode {} message: {}",
+            message_type, error
+        ))
+    })?;
+    serde_json::to_string_pretty(&message).map_err(|error| {
+        AppError::Parse(format!(
+            "Failed to serialize {} message: {}",
+            message_type, error
+        ))
+    })
+}
+
+pub(crate) async fn unary(call: GrpcCall) -> Result<GrpcResponse, AppError> {
+    let pool = load_descriptor_pool(&call.descriptor_path)?;
+    let method = find_method(&pool, &call.method)?;
+
+    let mut deserializer = serde_json::Deserializer::from_str(&call.request_json);
+    let message =
+        DynamicMessage::deserialize(method.input(), &mut deserializer).map_err(|error| {
+            AppError::Parse(format!(
+                "Failed to parse request JSON as {}: {}",
+                method.input().full_name(),
+                error
+            ))
+        })?;
+
+    let mut endpoint = Endpoint::from_shared(call.endpoint.clone()).map_err(|error| {
+        AppError::Validation(format!(
+            "Invalid gRPC endpoint {}: {}",
+            call.endpoint, error
+        ))
+    })?;
+    if call.endpoint.starts_with("https://") {
+        endpoint = endpoint
+            .tls_config(ClientTlsConfig::new().with_webpki_roots())
+            .map_err(|error| AppError::Io(format!("Failed to configure TLS: {}", error)))?;
+    }
+    let channel = endpoint.connect().await.map_err(|error| {
+        AppError::Network(format!("Failed to connect to {}: {}", call.endpoint, error))
+    })?;
+
+    let path = PathAndQuery::from_str(&format!(
+        "/{}/{}",
+        method.parent_service().full_name(),
+        method.name()
+    ))
+    .map_err(|error| AppError::Validation(format!("Invalid gRPC path: {}", error)))?;
+
+    let mut client = tonic::client::Grpc::new(channel);
+    client
+        .ready()
+        .await
+        .map_err(|error| AppError::Network(format!("gRPC channel not ready: {}", error)))?;
+
+    match client
+        .unary(
+            Request::new(message),
+            path,
+            DynamicCodec::new(method.output()),
+        )
+        .await
+    {
+        Ok(response) => {
+            let metadata = metadata_to_map(response.metadata());
+            let body = serde_json::to_string(response.get_ref()).map_err(|error| {
+                AppError::Parse(format!("Failed to serialize response message: {}", error))
+            })?;
+            Ok(GrpcResponse {
+                message: Some(body),
+                status_code: tonic::Code::Ok as i32,
+                status: format!("{:?}", tonic::Code::Ok),
+                status_message: String::new(),
+                metadata,
+            })
+        }
+        Err(status) => Ok(GrpcResponse {
+            message: None,
+            status_code: status.code() as i32,
+            status: format!("{:?}", status.code()),
+            status_message: status.message().to_string(),
+            metadata: metadata_to_map(status.metadata()),
+        }),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::convert::Infallible;
+    use std::task::{Context, Poll};
+    use std::time::{SystemTime, UNIX_EPOCH};
+    use tonic::body::BoxBody;
+    use tonic::codegen::{http, BoxFuture, Service};
+    use tonic::server::{Grpc, NamedService, UnaryService};
+
+    const ECHO_PROTO: &str = r#"
+syntax = "proto3";
+package test;
+
+message EchoRequest { string message = 1; }
+message EchoReply { string message = 1; int32 length = 2; }
+
+service Echo {
+  rpc Say(EchoRequest) returns (EchoReply);
+  rpc Fail(EchoRequest) returns (EchoReply);
+}
+"#;
+
+    #[derive(Clone)]
+    struct EchoServer {
+        pool: DescriptorPool,
+    }
+
+    impl NamedService for EchoServer {
+        const NAME: &'static str = "test.Echo";
+    }
+
+    struct EchoMethod {
+        reply: MessageDescriptor,
+        fail: bool,
+    }
+
+    impl UnaryService<DynamicMessage> for EchoMethod {
+        type Response = DynamicMessage;
+        type Future = BoxFuture<tonic::Response<DynamicMessage>, Status>;
+
+        fn call(&mut self, request: Request<DynamicMessage>) -> Self::Future {
+            let reply = self.reply.clone();
+            let fail = self.fail;
+            Box::pin(async move {
+                if fail {
+                    let mut status = Status::not_found("no such greeting");
+                    status
+                        .metadata_mut()
+                        .insert("x-reason", "test".parse().expect("metadata value"));
+                    return Err(status);
+                }
+
+                let text = request
+                    .get_ref()
+                    .get_field_by_name("message")
+                    .and_then(|value| value.as_str().map(str::to_string))
+                    .unwrap_or_default();
+                let mut message = DynamicMessage::new(reply);
+                message.set_field_by_name(
+                    "message",
+                    prost_reflect::Value::String(format!("hello {}", text)),
+                );
+                message.set_field_by_name("length", prost_reflect::Value::I32(text.len() as i32));
+                Ok(tonic::Response::new(message))
+            })
+        }
+    }
+
+    impl Service<http::Request<BoxBody>> for EchoServer {
+        type Response = http::Response<BoxBody>;
+        type Error = Infallible;
+        type Future = BoxFuture<Self::Response, Self::Error>;
+
+        fn poll_ready(&mut self, _cx: &mut Context<'_>) -> Poll<Result<(), Self::Error>> {
+            Poll::Ready(Ok(()))
+        }
+
+        fn call(&mut self, request: http::Request<BoxBody>) -> Self::Future {
+            let service = self.pool.get_service_by_name("test.Echo").expect("service");
+            let method_name = request.uri().path().rsplit('/').next().unwrap_or_default();
+            let method = service
+                .methods()
+                .find(|method| method.name() == method_name)
+                .expect("method");
+            let handler = EchoMethod {
+                reply: method.output(),
+                fail: method.name() == "Fail",
+            };
+            let codec = DynamicCodec::new(method.input());
+            Box::pin(async move {
+                let mut grpc = Grpc::new(codec);
+                Ok(grpc.unary(handler, request).await)
+            })
+        }
+    }
+
+    fn write_proto() -> std::path::PathBuf {
+        let nanos = SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .map(|duration| duration.as_nanos())
+            .unwrap_or(0);
+        let dir =
+            std::env::temp_dir().join(format!("eshttp-grpc-{}-{}", std::process::id(), nanos));
+        fs::create_dir_all(&dir).expect("create proto dir");
+        let proto = dir.join("echo.proto");
+        fs::write(&proto, ECHO_PROTO).expect("write proto");
+        proto
+    }
+
+    async fn start_server(pool: DescriptorPool) -> String {
+        let listener = tokio::net::TcpListener::bind("127.0.0.1:0")
+            .await
+            .expect("bind test listener");
+        let address = listener.local_addr().expect("local addr");
+        tokio::spawn(async move {
+            tonic::transport::Server::builder()
+                .add_service(EchoServer { pool })
+                .serve_with_incoming(tokio_stream::wrappers::TcpListenerStream::new(listener))
+                .await
+                .expect("serve test grpc");
+        });
+
+        format!("http://{}", address)
+    }
+
+    #[tokio::test]
+    async fn unary_call_round_trips_json_through_descriptors() {
+        let proto = write_proto();
+        let pool = load_descriptor_pool(&proto.to_string_lossy()).expect("load proto");
+        let endpoint = start_server(pool.clone()).await;
+
+        let descriptor_set = proto.with_extension("pb");
+        let encoded = protox::compile(["echo.proto"], [proto.parent().expect("parent")])
+            .expect("compile proto")
+            .encode_to_vec();
+        fs::write(&descriptor_set, encoded).expect("write descriptor set");
+
+        let response = unary(GrpcCall {
+            endpoint: endpoint.clone(),
+            descriptor_path: descriptor_set.to_string_lossy().to_string(),
+            method: "test.Echo/Say".to_string(),
+            request_json: r#"{"message":"grpc"}"#.to_string(),
+        })
+        .await
+        .expect("unary call");
+        assert_eq!(response.status_code, 0);
+        let body: serde_json::Value =
+            serde_json::from_str(response.message.as_deref().expect("message")).expect("json");
+        assert_eq!(body["message"], "hello grpc");
+        assert_eq!(body["length"], 4);
+
+        let failed = unary(GrpcCall {
+            endpoint,
+            descriptor_path: proto.to_string_lossy().to_string(),
+            method: "test.Echo.Fail".to_string(),
+            request_json: "{}".to_string(),
+        })
+        .await
+        .expect("unary call with status");
+        assert_eq!(failed.status, "NotFound");
+        assert_eq!(failed.status_message, "no such greeting");
+        assert_eq!(
+            failed.metadata.get("x-reason").map(String::as_str),
+            Some("test")
+        );
+        assert!(failed.message.is_none());
+
+        let _ = fs::remove_dir_all(proto.parent().expect("parent"));
+    }
+
+    #[test]
+    fn decode_message_renders_json_from_descriptor_set() {
+        let proto = write_proto();
+        let pool = load_descriptor_pool(&proto.to_string_lossy()).expect("load proto");
+        let descriptor_set = protox::compile(["echo.proto"], [proto.parent().expect("parent")])
+            .expect("compile proto")
+            .encode_to_vec();
+
+        let mut message =
+            DynamicMessage::new(pool.get_message_by_name("test.EchoReply").expect("message"));
+        message.set_field_by_name("message", prost_reflect::Value::String("hi".to_string()));
+        message.set_field_by_name("length", prost_reflect::Value::I32(2));
+
+        let decoded = decode_message(
+            &BASE64_STANDARD.encode(message.encode_to_vec()),
+            &BASE64_STANDARD.encode(&descriptor_set),
+            "test.EchoReply",
+        )
+        .expect("decode message");
+        let json: serde_json::Value = serde_json::from_str(&decoded).expect("json");
+        assert_eq!(json, serde_json::json!({ "message": "hi", "length": 2 }));
+
+        let missing = decode_message("", &BASE64_STANDARD.encode(&descriptor_set), "test.Missing");
+        assert!(
+            matches!(missing, Err(AppError::Validation(message)) if message.contains("not found"))
+        );
+        let corrupt = decode_message(
+            &BASE64_STANDARD.encode([0xff, 0xff]),
+            &BASE64_STANDARD.encode(&descriptor_set),
+            "test.EchoReply",
+        );
+        assert!(corrupt.is_err());
+
+        let _ = fs::remove_dir_all(proto.parent().expect("parent"));
+    }
+
+    #[test]
+    fn find_method_rejects_unknown_methods() {
+        let proto = write_proto();
+        let pool = load_descriptor_pool(&proto.to_string_lossy()).expect("load proto");
+
+        assert!(find_method(&pool, "test.Echo/Say").is_ok());
+        assert!(find_method(&pool, "test.Echo/Missing").is_err());
+        assert!(find_method(&pool, "test.Missing/Say").is_err());
+        assert!(find_method(&pool, "Say").is_err());
+
+        let _ = fs::remove_dir_all(proto.parent().expect("parent"));
+    }
+}
@@ -0,0 +1,361 @@
+use crate::AppError;
+use hmac::{Hmac, Mac};
+use md4::{Digest, Md4};
+use md5::Md5;
+use std::time::{SystemTime, UNIX_EPOCH};
+
+type HmacMd5 = Hmac<Md5>;
+
+const SIGNATURE: &[u8; 8] = b"NTLMSSP\0";
+const NEGOTIATE_UNICODE: u32 = 0x0000_0001;
+const NEGOTIATE_OEM: u32 = 0x0000_0002;
+const REQUEST_TARGET: u32 = 0x0000_0004;
+const NEGOTIATE_NTLM: u32 = 0x0000_0200;
+const NEGOTIATE_ALWAYS_SIGN: u32 = 0x0000_8000;
+const NEGOTIATE_EXTENDED_SESSION_SECURITY: u32 = 0x0008_0000;
+const AV_EOL: u16 = 0;
+const AV_TIMESTAMP: u16 = 7;
+/// Seconds between 1601-01-01 (FILETIME epoch) and 1970-01-01.
+const FILETIME_UNIX_OFFSET_SECS: u64 = 11_644_473_600;
+
+/// The server's CHALLENGE_MESSAGE (type 2).
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub(crate) struct Challenge {
+    pub(crate) flags: u32,
+    pub(crate) server_challenge: [u8; 8],
+    pub(crate) target_info: Vec<u8>,
+}
+
+pub(crate) struct Credentials<'a> {
+    pub(crate) username: &'a str,
+    pub(crate) password: &'a str,
+    pub(crate) domain: &'a str,
+}
+
+fn utf16le(text: &str) -> Vec<u8> {
+    text.encode_utf16().flat_map(u16::to_le_bytes).collect()
+}
+
+fn hmac_md5(key: &[u8], parts: &[&[u8]]) -> [u8; 16] {
+    let mut mac = HmacMd5::new_from_slice(key).expect("HMAC accepts keys of any length");
+    for part in parts {
+        mac.update(part);
+    }
+    mac.finalize().into_bytes().into()
+}
+
+fn read_u16(message: &[u8], offset: usize) -> Option<u16> {
+    message
+        .get(offset..offset + 2)
+        .map(|bytes| u16::from_le_bytes([bytes[0], bytes[1]]))
+}
+
+fn read_u32(message: &[u8], offset: usize) -> Option<u32> {
+    message
+        .get(offset..offset + 4)
+        .map(|bytes| u32::from_le_bytes([bytes[0], bytes[1], bytes[2], bytes[3]]))
+}
+
+/// Reads the payload referenced by the security buffer (`len`, `max_len`, `offset`)
+/// starting at `field`.
+fn read_security_buffer(message: &[u8], field: usize) -> Option<&[u8]> {
+    let length = read_u16(message, field)? as usize;
+    let offset = read_u32(message, field + 4)? as usize;
+    message.get(offset..offset.checked_add(length)?)
+}
+
+/// NEGOTIATE_MESSAGE (type 1) without domain, workstation, or version fields.
+pub(crate) fn negotiate_message() -> Vec<u8> {
+    let flags = NEGOTIATE_UNICODE
+        | NEGOTIATE_OEM
+        | REQUEST_TARGET
+        | NEGOTIATE_NTLM
+        | NEGOTIATE_ALWAYS_SIGN
+        | NEGOTIATE_EXTENDED_SESSION_SECURITY;
+
+    let mut message = Vec::with_capacity(32);
+    message.extend_from_slice(SIGNATURE);
+    message.extend_from_slice(&1_u32.to_le_bytes());
+    message.extend_from_slice(&flags.to_le_bytes());
+    message.extend_from_slice(&[0; 16]);
+    message
+}
+
+pub(crate) fn parse_challenge(message: &[u8]) -> Result<Challenge, AppError> {
+    let invalid = || AppError::Parse("Invalid NTLM challenge message".to_string());
+
+    if message.get(..8) != Some(SIGNATURE.as_slice()) || read_u32(message, 8) != Some(2) {
+        return Err(invalid());
+    }
+    let flags = read_u32(message, 20).ok_or_else(invalid)?;
+    let server_challenge: [u8; 8] = message
+        .get(24..32)
+        .and_then(|bytes| bytes.try_into().ok())
+        .ok_or_else(invalid)?;
+    let target_info = if message.len() >= 48 {
+        read_security_buffer(message, 40)
+            .ok_or_else(invalid)?
+            .to_vec()
+    } else {
+        Vec::new()
+    };
+
+    Ok(Challenge {
+        flags,
+        server_challenge,
+        target_info,
+    })
+}
+
+/// `MsvAvTimestamp` from the challenge's target info, when the server sent one.
+pub(crate) fn timestamp_from_target_info(target_info: &[u8]) -> Option<u64> {
+    let mut offset = 0;
+    while let (Some(id), Some(length)) = (
+        read_u16(target_info, offset),
+        read_u16(target_info, offset + 2),
+    ) {
+        let value = target_info.get(offset + 4..offset + 4 + length as usize)?;
+        match id {
+            AV_EOL => return None,
+            AV_TIMESTAMP => return value.try_into().ok().map(u64::from_le_bytes),
+            _ => offset += 4 + length as usize,
+        }
+    }
+    None
+}
+
+/// Current time as a Windows FILETIME (100ns ticks since 1601-01-01).
+pub(crate) fn current_filetime() -> u64 {
+    let since_unix = SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .unwrap_or_default();
+    (since_unix.as_secs() + FILETIME_UNIX_OFFSET_SECS) * 10_000_000
+        + u64::from(since_unix.subsec_nanos() / 100)
+}
+
+pub(crate) fn random_client_challenge() -> Result<[u8; 8], AppError> {
+    let mut challenge = [0_u8; 8];
+    getrandom::getrandom(&mut challenge).map_err(|error| {
+        AppError::Io(format!(
+            "Failed to generate NTLM client challenge: {}",
+            error
+        ))
+    })?;
+    Ok(challenge)
+}
+
+/// NTOWFv2: HMAC-MD5 keyed with the NT hash over `UPPER(user) + domain` in UTF-16LE.
+pub(crate) fn ntowf_v2(credentials: &Credentials<'_>) -> [u8; 16] {
+    let nt_hash = Md4::digest(utf16le(credentials.password));
+    let identity = utf16le(&format!(
+        "{}{}",
+        credentials.username.to_uppercase(),
+        credentials.domain
+    ));
+    hmac_md5(&nt_hash, &[&identity])
+}
+
+pub(crate) fn lmv2_response(
+    response_key: &[u8; 16],
+    server_challenge: &[u8; 8],
+    client_challenge: &[u8; 8],
+) -> Vec<u8> {
+    let mut response = hmac_md5(response_key, &[server_challenge, client_challenge]).to_vec();
+    response.extend_from_slice(client_challenge);
+    response
+}
+
+/// NTLMv2 response: `NTProofStr` followed by the client blob it was computed over.
+pub(crate) fn ntlmv2_response(
+    response_key: &[u8; 16],
+    server_challenge: &[u8; 8],
+    client_challenge: &[u8; 8],
+    timestamp: u64,
+    target_info: &[u8],
+) -> Vec<u8> {
+    let mut blob = vec![1, 1, 0, 0, 0, 0, 0, 0];
+    blob.extend_from_slice(&timestamp.to_le_bytes());
+    blob.extend_from_slice(client_challenge);
+    blob.extend_from_slice(&[0; 4]);
+    blob.extend_from_slice(target_info);
+    blob.extend_from_slice(&[0; 4]);
+
+    let mut response = hmac_md5(response_key, &[server_challenge, &blob]).to_vec();
+    response.extend_from_slice(&blob);
+    response
+}
+
+/// AUTHENTICATE_MESSAGE (type 3) answering `challenge` with NTLMv2 responses.
+pub(crate) fn authenticate_message(
+    challenge: &Challenge,
+    credentials: &Credentials<'_>,
+    client_challenge: &[u8; 8],
+    timestamp: u64,
+) -> Vec<u8> {
+    let response_key = ntowf_v2(credentials);
+    let lm_response = lmv2_response(&response_key, &challenge.server_challenge, client_challenge);
+    let nt_response = ntlmv2_response(
+        &response_key,
+        &challenge.server_challenge,
+        client_challenge,
+        timestamp,
+        &challenge.target_info,
+    );
+    let payloads = [
+        lm_response,
+        nt_response,
+        utf16le(credentials.domain),
+        utf16le(credentials.username),
+        Vec::new(),
+        Vec::new(),
+    ];
+
+    const HEADER_LENGTH: usize = 64;
+    let mut message =
+        Vec::with_capacity(HEADER_LENGTH + payloads.iter().map(Vec::len).sum::<usize>());
+    message.extend_from_slice(SIGNATURE);
+    message.extend_from_slice(&3_u32.to_le_bytes());
+
+    let mut offset = HEADER_LENGTH;
+    for payload in &payloads {
+        let length = payload.len() as u16;
+        message.extend_from_slice(&length.to_le_bytes());
+        message.extend_from_slice(&length.to_le_bytes());
+        message.extend_from_slice(&(offset as u32).to_le_bytes());
+        offset += payload.len();
+    }
+
+    let flags = NEGOTIATE_UNICODE
+        | NEGOTIATE_NTLM
+        | NEGOTIATE_ALWAYS_SIGN
+        | NEGOTIATE_EXTENDED_SESSION_SECURITY
+        | (challenge.flags & REQUEST_TARGET);
+    message.extend_from_slice(&flags.to_le_bytes());
+    for payload in &payloads {
+        message.extend_from_slice(payload);
+    }
+    message
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    // Test vectors from [MS-NLMP] 4.2.1 and 4.2.4.
+    const CREDENTIALS: Credentials<'static> = Credentials {
+        username: "User",
+        password: "Password",
+        domain: "Domain",
+    };
+    const SERVER_CHALLENGE: [u8; 8] = [0x01, 0x23, 0x45, 0x67, 0x89, 0xab, 0xcd, 0xef];
+    const CLIENT_CHALLENGE: [u8; 8] = [0xaa; 8];
+
+    fn hex(bytes: &[u8]) -> String {
+        bytes.iter().map(|byte| format!("{:02x}", byte)).collect()
+    }
+
+    fn av_pair(id: u16, value: &[u8]) -> Vec<u8> {
+        let mut pair = id.to_le_bytes().to_vec();
+        pair.extend_from_slice(&(value.len() as u16).to_le_bytes());
+        pair.extend_from_slice(value);
+        pair
+    }
+
+    fn spec_target_info() -> Vec<u8> {
+        let mut target_info = av_pair(2, &utf16le("Domain"));
+        target_info.extend(av_pair(1, &utf16le("Server")));
+        target_info.extend(av_pair(AV_EOL, &[]));
+        target_info
+    }
+
+    fn spec_challenge_message() -> Vec<u8> {
+        let target_name = utf16le("Server");
+        let target_info = spec_target_info();
+        let mut message = SIGNATURE.to_vec();
+        message.extend_from_slice(&2_u32.to_le_bytes());
+        message.extend_from_slice(&(target_name.len() as u16).to_le_bytes());
+        message.extend_from_slice(&(target_name.len() as u16).to_le_bytes());
+        message.extend_from_slice(&56_u32.to_le_bytes());
+        message.extend_from_slice(&0xe28a_8233_u32.to_le_bytes());
+        message.extend_from_slice(&SERVER_CHALLENGE);
+        message.extend_from_slice(&[0; 8]);
+        message.extend_from_slice(&(target_info.len() as u16).to_le_bytes());
+        message.extend_from_slice(&(target_info.len() as u16).to_le_bytes());
+        message.extend_from_slice(&((56 + target_name.len()) as u32).to_le_bytes());
+        message.extend_from_slice(&[0x06, 0x00, 0x70, 0x17, 0x00, 0x00, 0x00, 0x0f]);
+        message.extend_from_slice(&target_name);
+        message.extend_from_slice(&target_info);
+        message
+    }
+
+    #[test]
+    fn hashes_match_spec_vectors() {
+        assert_eq!(
+            hex(&Md4::digest(utf16le("Password"))),
+            "a4f49c406510bdcab6824ee7c30fd852"
+        );
+        assert_eq!(
+            hex(&ntowf_v2(&CREDENTIALS)),
+            "0c868a403bfd7a93a3001ef22ef02e3f"
+        );
+    }
+
+    #[test]
+    fn ntlmv2_responses_match_spec_vectors() {
+        let key = ntowf_v2(&CREDENTIALS);
+        assert_eq!(
+            hex(&lmv2_response(&key, &SERVER_CHALLENGE, &CLIENT_CHALLENGE)),
+            "86c35097ac9cec102554764a57cccc19aaaaaaaaaaaaaaaa"
+        );
+
+        let challenge = parse_challenge(&spec_challenge_message()).expect("parse challenge");
+        assert_eq!(challenge.server_challenge, SERVER_CHALLENGE);
+        assert_eq!(challenge.target_info, spec_target_info());
+
+        let response = ntlmv2_response(
+            &key,
+            &challenge.server_challenge,
+            &CLIENT_CHALLENGE,
+            0,
+            &challenge.target_info,
+        );
+        assert_eq!(hex(&response[..16]), "68cd0ab851e51c96aabc927bebef6a1c");
+    }
+
+    #[test]
+    fn messages_have_expected_layout() {
+        let negotiate = negotiate_message();
+        assert_eq!(&negotiate[..8], SIGNATURE);
+        assert_eq!(read_u32(&negotiate, 8), Some(1));
+        assert_eq!(negotiate.len(), 32);
+
+        let challenge = parse_challenge(&spec_challenge_message()).expect("parse challenge");
+        let message = authenticate_message(&challenge, &CREDENTIALS, &CLIENT_CHALLENGE, 0);
+        assert_eq!(read_u32(&message, 8), Some(3));
+        assert_eq!(
+            read_security_buffer(&message, 12).map(<[u8]>::len),
+            Some(24)
+        );
+        assert_eq!(
+            read_security_buffer(&message, 28),
+            Some(utf16le("Domain").as_slice())
+        );
+        assert_eq!(
+            read_security_buffer(&message, 36),
+            Some(utf16le("User").as_slice())
+        );
+
+        assert!(parse_challenge(&negotiate).is_err());
+        assert!(parse_challenge(b"NTLMSSP\0\x02\0\0\0").is_err());
+    }
+
+    #[test]
+    fn timestamp_is_read_from_target_info() {
+        let mut target_info = av_pair(2, &utf16le("Domain"));
+        target_info.extend(av_pair(AV_TIMESTAMP, &42_u64.to_le_bytes()));
+        target_info.extend(av_pair(AV_EOL, &[]));
+
+        assert_eq!(timestamp_from_target_info(&target_info), Some(42));
+        assert_eq!(timestamp_from_target_info(&spec_target_info()), None);
+    }
+}
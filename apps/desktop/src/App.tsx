@@ -447,6 +447,7 @@ export function App() {
   const [queryRows, setQueryRows] = useState<KeyValueRow[]>([]);
   const [headerRows, setHeaderRows] = useState<KeyValueRow[]>([]);
   const [bearerToken, setBearerToken] = useState("");
+  const [fileVariables, setFileVariables] = useState<Record<string, string>>({});
 
   const [bodyMode, setBodyMode] = useState<BodyMode>("editor");
   const [payloadLanguage, setPayloadLanguage] = useState<PayloadLanguage>("json");
@@ -619,6 +620,7 @@ export function App() {
       }
 
       setHeaderRows(headersToRows(nextHeaders));
+      setFileVariables(parsed.fileVariables);
 
       const nextBody = parsed.body ?? "";
       setEditorBody(nextBody);
@@ -633,6 +635,7 @@ export function App() {
       setQueryRows([]);
       setHeaderRows([]);
       setBearerToken("");
+      setFileVariables({});
       setEditorBody(text);
       setPayloadLanguage(detectPayloadLanguage(text));
       setBodyMode("editor");
@@ -860,6 +863,7 @@ export function App() {
         requestText: composedRequestText,
         workspaceEnvText,
         collectionEnvText,
+        fileVariables,
       });
 
       const response = await transport.send({
@@ -1,5 +1,5 @@
 import type { Collection, RequestFile, Workspace } from "@eshttp/core";
-import { invokeTauri, isTauriRuntime } from "../runtime";
+import { invokeTauri, isTauriRuntime, type TauriDiscoveryResult } from "../runtime";
 import { getCollectionIconOption, renderCollectionIconSvg } from "./collectionIcons";
 import {
   type CacheCollectionRecord,
@@ -956,9 +956,12 @@ export class CollectionsRepository {
       uri: importRecord.path,
     };
 
-    const discovered = await invokeTauri<Collection[]>("discover_collections", {
-      workspace: discoveryWorkspace,
-    });
+    const { collections: discovered } = await invokeTauri<TauriDiscoveryResult>(
+      "discover_collections",
+      {
+        workspace: discoveryWorkspace,
+      },
+    );
 
     const collections: WorkspaceTreeNode["collections"] = [];
 
@@ -983,6 +986,7 @@ export class CollectionsRepository {
 
       const discoveredRequests = await invokeTauri<RequestFile[]>("list_requests", {
         collection: discoveredCollection,
+        workspace: discoveryWorkspace,
       });
 
       const requests = discoveredRequests.map((request) => {
@@ -1284,7 +1288,7 @@ export class CollectionsRepository {
       uri: importRecord.path,
     };
 
-    const collections = await invokeTauri<Collection[]>("discover_collections", {
+    const { collections } = await invokeTauri<TauriDiscoveryResult>("discover_collections", {
       workspace: discoverWorkspace,
     });
 
@@ -1294,6 +1298,7 @@ export class CollectionsRepository {
       const relativePathValue = relativePath(importRecord.path, collection.uri);
       const requests = await invokeTauri<RequestFile[]>("list_requests", {
         collection,
+        workspace: discoverWorkspace,
       });
 
       const cachedRequests: CacheRequestRecord[] = [];
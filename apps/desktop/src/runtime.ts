@@ -1,8 +1,57 @@
+import type { Collection } from "@eshttp/core";
+
 export function isTauriRuntime(): boolean {
   return typeof window !== "undefined" && "__TAURI_INTERNALS__" in window;
 }
 
+export type TauriCommandErrorKind =
+  | "io"
+  | "validation"
+  | "network"
+  | "git"
+  | "parse"
+  | "scopeViolation"
+  | "tooLarge";
+
+export class TauriCommandError extends Error {
+  readonly kind: TauriCommandErrorKind;
+
+  constructor(kind: TauriCommandErrorKind, message: string) {
+    super(message);
+    this.name = "TauriCommandError";
+    this.kind = kind;
+  }
+}
+
+/**
+ * Result of the `discover_collections` command; `skipped` lists unreadable directories and
+ * `warnings` the invalid config values discovery ignored.
+ */
+export interface TauriDiscoveryResult {
+  collections: Collection[];
+  skipped: string[];
+  warnings: string[];
+}
+
+function isCommandErrorPayload(
+  value: unknown,
+): value is { kind: TauriCommandErrorKind; message: string } {
+  return (
+    typeof value === "object" &&
+    value !== null &&
+    typeof (value as { kind?: unknown }).kind === "string" &&
+    typeof (value as { message?: unknown }).message === "string"
+  );
+}
+
 export async function invokeTauri<T>(command: string, args?: Record<string, unknown>): Promise<T> {
   const { invoke } = await import("@tauri-apps/api/core");
-  return invoke<T>(command, args);
+  try {
+    return await invoke<T>(command, args);
+  } catch (error) {
+    if (isCommandErrorPayload(error)) {
+      throw new TauriCommandError(error.kind, error.message);
+    }
+    throw error;
+  }
 }
@@ -1,5 +1,5 @@
 import type { Collection, CollectionSource, RequestFile, Workspace } from "@eshttp/core";
-import { invokeTauri, isTauriRuntime } from "./runtime";
+import { invokeTauri, isTauriRuntime, type TauriDiscoveryResult } from "./runtime";
 
 export class TauriCollectionSource implements CollectionSource {
   readonly kind = "tauri-fs";
@@ -9,7 +9,10 @@ export class TauriCollectionSource implements CollectionSource {
   }
 
   async discoverCollections(workspace: Workspace): Promise<Collection[]> {
-    return invokeTauri("discover_collections", { workspace });
+    const { collections } = await invokeTauri<TauriDiscoveryResult>("discover_collections", {
+      workspace,
+    });
+    return collections;
   }
 
   async listRequests(collection: Collection): Promise<RequestFile[]> {
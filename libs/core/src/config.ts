@@ -1,3 +1,4 @@
+import { ENV_NAME_PATTERN } from "./env";
 import { EshttpError } from "./errors";
 import { type DiscoveryConfig, DiscoveryConfigSchema } from "./schemas";
 
@@ -5,13 +6,13 @@ function escapeRegex(value: string): string {
   return value.replace(/[.+^${}()|[\]\\]/g, "\\$&");
 }
 
-function globToRegex(pattern: string): RegExp {
+function globToRegex(pattern: string, caseInsensitive = false): RegExp {
   const escaped = escapeRegex(pattern)
     .replace(/\*\*/g, "::DOUBLE_STAR::")
     .replace(/\*/g, "[^/]*")
     .replace(/::DOUBLE_STAR::/g, ".*");
 
-  return new RegExp(`^${escaped}$`);
+  return new RegExp(`^${escaped}$`, caseInsensitive ? "i" : "");
 }
 
 export function parseDiscoveryConfig(rawText: string | undefined): DiscoveryConfig | null {
@@ -37,7 +38,13 @@ export function parseDiscoveryConfig(rawText: string | undefined): DiscoveryConf
     );
   }
 
-  return parsed.data;
+  // An invalid default environment is dropped rather than failing the whole config,
+  // matching the desktop backend.
+  const { defaultEnvironment, ...config } = parsed.data;
+  if (defaultEnvironment === undefined || !ENV_NAME_PATTERN.test(defaultEnvironment)) {
+    return config;
+  }
+  return { ...config, defaultEnvironment };
 }
 
 export function pathIncludedByConfig(
@@ -50,7 +57,10 @@ export function pathIncludedByConfig(
 
   const normalizedPath = relativePath.replaceAll("\\", "/");
 
-  const excluded = config.exclude.some((pattern) => globToRegex(pattern).test(normalizedPath));
+  const caseInsensitive = config.caseInsensitive ?? false;
+  const excluded = config.exclude.some((pattern) =>
+    globToRegex(pattern, caseInsensitive).test(normalizedPath),
+  );
   if (excluded) {
     return false;
   }
@@ -59,7 +69,9 @@ export function pathIncludedByConfig(
     return true;
   }
 
-  return config.include.some((pattern) => globToRegex(pattern).test(normalizedPath));
+  return config.include.some((pattern) =>
+    globToRegex(pattern, caseInsensitive).test(normalizedPath),
+  );
 }
 
 export function getEntryPatterns(config: DiscoveryConfig | null): string[] {
@@ -72,5 +84,8 @@ export function getEntryPatterns(config: DiscoveryConfig | null): string[] {
 
 export function matchesEntryPattern(relativePath: string, config: DiscoveryConfig | null): boolean {
   const normalizedPath = relativePath.replaceAll("\\", "/");
-  return getEntryPatterns(config).some((pattern) => globToRegex(pattern).test(normalizedPath));
+  const caseInsensitive = config?.caseInsensitive ?? false;
+  return getEntryPatterns(config).some((pattern) =>
+    globToRegex(pattern, caseInsensitive).test(normalizedPath),
+  );
 }
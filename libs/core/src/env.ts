@@ -1,3 +1,10 @@
+import { EshttpError } from "./errors";
+
+// Key naming the environment an environment file extends.
+export const ENV_EXTENDS_KEY = "_extends";
+
+export const ENV_NAME_PATTERN = /^[A-Za-z0-9_.-]+$/;
+
 export function parseEnvText(text: string): Record<string, string> {
   const result: Record<string, string> = {};
   const normalized = text.replace(/\r\n/g, "\n");
@@ -29,6 +36,52 @@ export function parseEnvText(text: string): Record<string, string> {
   return result;
 }
 
+export function renderEnvText(values: Record<string, string>): string {
+  return Object.keys(values)
+    .sort()
+    .map((key) => `${key}="${values[key]}"`)
+    .join("\n");
+}
+
+// Merges `envName` over the environment its `_extends` key names, recursively, the
+// same way the desktop backend does. `readText` returns an environment's raw file
+// text, or null when it has none.
+export async function resolveEnvironment(
+  envName: string,
+  readText: (envName: string) => Promise<string | null>,
+  chain: string[] = [],
+): Promise<Record<string, string> | null> {
+  if (!ENV_NAME_PATTERN.test(envName)) {
+    throw new EshttpError("ENV_RESOLUTION_ERROR", `Invalid environment name: ${envName}`);
+  }
+  if (chain.includes(envName)) {
+    throw new EshttpError(
+      "ENV_RESOLUTION_ERROR",
+      `Environment inheritance cycle: ${[...chain, envName].join(" -> ")}`,
+    );
+  }
+
+  const text = await readText(envName);
+  if (text === null) {
+    return null;
+  }
+
+  const { [ENV_EXTENDS_KEY]: parent, ...values } = parseEnvText(text);
+  if (parent === undefined) {
+    return values;
+  }
+
+  const inherited = await resolveEnvironment(parent, readText, [...chain, envName]);
+  if (!inherited) {
+    throw new EshttpError(
+      "ENV_RESOLUTION_ERROR",
+      `Environment ${envName} extends missing environment ${parent}`,
+    );
+  }
+
+  return { ...inherited, ...values };
+}
+
 export function mergeEnvironment(
   workspaceEnv: Record<string, string>,
   collectionEnv: Record<string, string>,
@@ -2,6 +2,7 @@ export type EshttpErrorCode =
   | "REQUEST_PARSE_ERROR"
   | "REQUEST_VALIDATION_ERROR"
   | "MISSING_ENV_VARIABLES"
+  | "ENV_RESOLUTION_ERROR"
   | "DISCOVERY_CONFIG_ERROR";
 
 export class EshttpError extends Error {
@@ -6,10 +6,16 @@ export interface ExecuteRequestInput {
   requestText: string;
   workspaceEnvText?: string;
   collectionEnvText?: string;
+  // `@name = value` definitions to apply when `requestText` no longer carries them.
+  fileVariables?: Record<string, string>;
 }
 
 export function buildRequest(input: ExecuteRequestInput) {
-  const parsedRequest = parseHttpRequestText(input.requestText, input.title);
+  const parsed = parseHttpRequestText(input.requestText, input.title);
+  const parsedRequest = {
+    ...parsed,
+    fileVariables: { ...input.fileVariables, ...parsed.fileVariables },
+  };
 
   const workspaceEnv = input.workspaceEnvText ? parseEnvText(input.workspaceEnvText) : {};
   const collectionEnv = input.collectionEnvText ? parseEnvText(input.collectionEnvText) : {};
@@ -6,12 +6,35 @@ import {
   ResolvedHttpRequestSchema,
 } from "./schemas";
 
-const PLACEHOLDER_PATTERN = /\{\{\s*([A-Z0-9_]+)\s*\}\}/g;
+// `{{KEY}}`, or `{{KEY:-fallback}}` with an inline default used when `KEY` has no value.
+// Only uppercase env keys; other `{{...}}` text is left as written.
+const PLACEHOLDER_PATTERN = /\{\{\s*([A-Z0-9_]+)\s*(?::-([^}]*))?\}\}/g;
+
+// Any `{{name}}`. Only names defined with `@name = value` are replaced through it.
+const FILE_VARIABLE_PATTERN = /\{\{\s*([^\s{}:]+)\s*\}\}/g;
 
 function normalizeText(input: string): string {
   return input.replace(/\r\n/g, "\n").trim();
 }
 
+// Front-matter metadata is read by the desktop backend; requests ignore it.
+function stripFrontMatter(input: string): string {
+  if (!input.startsWith("---\n")) {
+    return input;
+  }
+
+  const lines = input.split("\n");
+  const closingIndex = lines.findIndex((line, index) => index > 0 && line.trim() === "---");
+  if (closingIndex < 0) {
+    throw new EshttpError("REQUEST_PARSE_ERROR", "Front-matter block is not closed.");
+  }
+
+  return lines
+    .slice(closingIndex + 1)
+    .join("\n")
+    .trim();
+}
+
 function parseRequestLine(line: string): { method: string; url: string } {
   const trimmed = line.trim();
   const match = trimmed.match(/^([A-Z]+)\s+(.+)$/);
@@ -38,6 +61,28 @@ function parseRequestLine(line: string): { method: string; url: string } {
   };
 }
 
+function parseFileVariableLine(line: string): { name: string; value: string } {
+  const definition = line.trim().slice(1);
+  const separatorIndex = definition.indexOf("=");
+  const name = separatorIndex < 0 ? "" : definition.slice(0, separatorIndex).trim();
+  if (!name || /\s/.test(name)) {
+    throw new EshttpError(
+      "REQUEST_PARSE_ERROR",
+      `Invalid variable definition: ${line}. Expected: @name = value`,
+    );
+  }
+
+  return { name, value: definition.slice(separatorIndex + 1).trim() };
+}
+
+// Replaces `{{name}}` for defined file variables and leaves everything else, inline
+// defaults included, for the environment to resolve.
+function interpolateFileVariables(template: string, variables: Record<string, string>): string {
+  return template.replaceAll(FILE_VARIABLE_PATTERN, (full, name: string) => {
+    return variables[name] ?? full;
+  });
+}
+
 function parseHeaderLine(line: string): { key: string; value: string } {
   const separatorIndex = line.indexOf(":");
   if (separatorIndex <= 0) {
@@ -53,13 +98,14 @@ function parseHeaderLine(line: string): { key: string; value: string } {
 }
 
 export function parseHttpRequestText(text: string, title: string): ParsedHttpRequest {
-  const normalized = normalizeText(text);
+  const normalized = stripFrontMatter(normalizeText(text));
   if (!normalized) {
     throw new EshttpError("REQUEST_PARSE_ERROR", "Request file is empty.");
   }
 
   const lines = normalized.split("\n");
 
+  const fileVariables: Record<string, string> = {};
   let currentLineIndex = 0;
   while (currentLineIndex < lines.length) {
     const candidate = lines[currentLineIndex];
@@ -68,6 +114,13 @@ export function parseHttpRequestText(text: string, title: string): ParsedHttpReq
     }
 
     const trimmedCandidate = candidate.trim();
+    if (trimmedCandidate.startsWith("@")) {
+      // Later definitions may reference earlier ones.
+      const { name, value } = parseFileVariableLine(candidate);
+      fileVariables[name] = interpolateFileVariables(value, fileVariables);
+      currentLineIndex += 1;
+      continue;
+    }
     if (!trimmedCandidate || trimmedCandidate.startsWith("#")) {
       currentLineIndex += 1;
       continue;
@@ -113,6 +166,7 @@ export function parseHttpRequestText(text: string, title: string): ParsedHttpReq
     url,
     headers,
     body,
+    fileVariables,
   });
 
   if (!parsed.success) {
@@ -131,8 +185,8 @@ function collectMissingPlaceholders(
 ): string[] {
   const missing = new Set<string>();
 
-  template.replaceAll(PLACEHOLDER_PATTERN, (_full, key: string) => {
-    if (!(key in environment)) {
+  template.replaceAll(PLACEHOLDER_PATTERN, (_full, key: string, fallback: string | undefined) => {
+    if (!(key in environment) && fallback === undefined) {
       missing.add(key);
     }
     return "";
@@ -142,15 +196,36 @@ function collectMissingPlaceholders(
 }
 
 function renderTemplate(template: string, environment: Record<string, string>): string {
-  return template.replaceAll(PLACEHOLDER_PATTERN, (_full, key: string) => {
-    return environment[key] ?? "";
-  });
+  return template.replaceAll(
+    PLACEHOLDER_PATTERN,
+    (_full, key: string, fallback: string | undefined) => {
+      return environment[key] ?? fallback?.trim() ?? "";
+    },
+  );
+}
+
+// Inlines file variables first, so they override the environment while the
+// environment still resolves any placeholders their values contain.
+function expandFileVariables(request: ParsedHttpRequest): ParsedHttpRequest {
+  const { fileVariables } = request;
+  return {
+    ...request,
+    url: interpolateFileVariables(request.url, fileVariables),
+    headers: Object.fromEntries(
+      Object.entries(request.headers).map(([key, value]) => [
+        key,
+        interpolateFileVariables(value, fileVariables),
+      ]),
+    ),
+    body: request.body ? interpolateFileVariables(request.body, fileVariables) : undefined,
+  };
 }
 
 export function resolveHttpRequest(
-  request: ParsedHttpRequest,
+  parsedRequest: ParsedHttpRequest,
   environment: Record<string, string>,
 ): ResolvedHttpRequest {
+  const request = expandFileVariables(parsedRequest);
   const missing = new Set<string>();
 
   for (const missingKey of collectMissingPlaceholders(request.url, environment)) {
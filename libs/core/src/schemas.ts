@@ -14,6 +14,7 @@ export const ParsedHttpRequestSchema = z.object({
   url: z.string().min(1),
   headers: HttpHeaderMapSchema.default({}),
   body: z.string().optional(),
+  fileVariables: z.record(z.string(), z.string()).default({}),
 });
 
 export const ResolvedHttpRequestSchema = ParsedHttpRequestSchema.extend({
@@ -25,6 +26,13 @@ export const DiscoveryConfigSchema = z
     entries: z.array(z.string().min(1)).default([]),
     include: z.array(z.string().min(1)).default([]),
     exclude: z.array(z.string().min(1)).default([]),
+    caseInsensitive: z.boolean().optional(),
+    symlinkAllowlist: z.array(z.string().min(1)).default([]),
+    displayName: z.string().min(1).optional(),
+    description: z.string().optional(),
+    icon: z.string().optional(),
+    color: z.string().optional(),
+    defaultEnvironment: z.string().optional(),
   })
   .strict();
 
@@ -32,6 +40,8 @@ export const WorkspaceSchema = z.object({
   id: z.string().min(1),
   name: z.string().min(1),
   uri: z.string().min(1),
+  icon: z.string().nullish(),
+  color: z.string().nullish(),
 });
 
 export const CollectionSchema = z.object({
@@ -39,6 +49,10 @@ export const CollectionSchema = z.object({
   workspaceId: z.string().min(1),
   name: z.string().min(1),
   uri: z.string().min(1),
+  description: z.string().nullish(),
+  icon: z.string().nullish(),
+  color: z.string().nullish(),
+  defaultEnvironment: z.string().nullish(),
 });
 
 export const RequestSchema = z.object({
@@ -46,6 +60,7 @@ export const RequestSchema = z.object({
   collectionId: z.string().min(1),
   title: z.string().min(1),
   uri: z.string().min(1),
+  relativePath: z.string().nullish(),
 });
 
 export type ParsedHttpRequest = z.infer<typeof ParsedHttpRequestSchema>;
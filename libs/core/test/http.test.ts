@@ -1,5 +1,12 @@
 import { describe, expect, test } from "bun:test";
-import { mergeEnvironment, parseEnvText, parseHttpRequestText, resolveHttpRequest } from "../src";
+import {
+  mergeEnvironment,
+  parseDiscoveryConfig,
+  parseEnvText,
+  parseHttpRequestText,
+  resolveEnvironment,
+  resolveHttpRequest,
+} from "../src";
 
 describe("parseHttpRequestText", () => {
   test("parses a request with headers and body", () => {
@@ -13,6 +20,37 @@ describe("parseHttpRequestText", () => {
     expect(parsed.headers.Authorization).toBe("Bearer {{TOKEN}}");
     expect(parsed.body).toBe('{"name":"Ada"}');
   });
+
+  test("skips YAML front-matter", () => {
+    const parsed = parseHttpRequestText(
+      "---\ntags: [smoke]\n---\nGET https://api.example.com/health\nAccept: application/json",
+      "Health",
+    );
+
+    expect(parsed.method).toBe("GET");
+    expect(parsed.headers.Accept).toBe("application/json");
+    expect(parsed.body).toBeUndefined();
+  });
+
+  test("skips file variable definitions before the request line", () => {
+    const parsed = parseHttpRequestText(
+      "@base = https://api.example.com\n# users\n@users = {{base}}/users\nGET {{users}}/1",
+      "User",
+    );
+
+    expect(parsed.method).toBe("GET");
+    expect(parsed.url).toBe("{{users}}/1");
+    expect(parsed.fileVariables).toEqual({
+      base: "https://api.example.com",
+      users: "https://api.example.com/users",
+    });
+  });
+
+  test("rejects a malformed file variable definition", () => {
+    const text = "@base https://api.example.com\nGET {{base}}";
+
+    expect(() => parseHttpRequestText(text, "Bad")).toThrow("Invalid variable definition");
+  });
 });
 
 describe("resolveHttpRequest", () => {
@@ -31,6 +69,42 @@ describe("resolveHttpRequest", () => {
     expect(resolved.headers["X-Api-Key"]).toBe("abc");
   });
 
+  test("renders file variables, which may reference the environment", () => {
+    const parsed = parseHttpRequestText(
+      "@base = https://{{HOST}}/v1\nGET {{base}}/users\nX-Base: {{base}}",
+      "Users",
+    );
+
+    const resolved = resolveHttpRequest(parsed, { HOST: "example.com", base: "ignored" });
+
+    expect(resolved.url).toBe("https://example.com/v1/users");
+    expect(resolved.headers["X-Base"]).toBe("https://example.com/v1");
+  });
+
+  test("falls back to inline defaults", () => {
+    const parsed = parseHttpRequestText(
+      "@base = https://{{HOST:-localhost}}:{{PORT:-8080}}\nGET {{base}}/{{PATH:- health }}",
+      "Health",
+    );
+
+    expect(resolveHttpRequest(parsed, {}).url).toBe("https://localhost:8080/health");
+    expect(resolveHttpRequest(parsed, { HOST: "example.com", PATH: "status" }).url).toBe(
+      "https://example.com:8080/status",
+    );
+  });
+
+  test("leaves lowercase and dotted placeholders that are not file variables as written", () => {
+    const parsed = parseHttpRequestText(
+      "@base = https://{{HOST}}\nGET {{base}}/{{version}}\nX-Token: {{login.response.headers.X}}",
+      "Chained",
+    );
+
+    const resolved = resolveHttpRequest(parsed, { HOST: "example.com" });
+
+    expect(resolved.url).toBe("https://example.com/{{version}}");
+    expect(resolved.headers["X-Token"]).toBe("{{login.response.headers.X}}");
+  });
+
   test("throws when variable is missing", () => {
     const parsed = parseHttpRequestText(`GET https://{{HOST}}/health`, "Health");
 
@@ -48,3 +122,46 @@ describe("parseEnvText and mergeEnvironment", () => {
     expect(merged.TOKEN).toBe("123");
   });
 });
+
+describe("resolveEnvironment", () => {
+  const files: Record<string, string> = {
+    base: "API_URL=https://api.example.com\nTIMEOUT=30",
+    prod: "_extends=base\nTIMEOUT=5",
+    "loop-a": "_extends=loop-b",
+    "loop-b": "_extends=loop-a",
+    orphan: "_extends=missing",
+    bad: "_extends=../base",
+  };
+  const readText = async (envName: string) => files[envName] ?? null;
+
+  test("child keys win over the extended environment", async () => {
+    expect(await resolveEnvironment("prod", readText)).toEqual({
+      API_URL: "https://api.example.com",
+      TIMEOUT: "5",
+    });
+    expect(await resolveEnvironment("none", readText)).toBeNull();
+  });
+
+  test("rejects cycles, missing parents, and invalid names", async () => {
+    await expect(resolveEnvironment("loop-a", readText)).rejects.toThrow(
+      "loop-a -> loop-b -> loop-a",
+    );
+    await expect(resolveEnvironment("orphan", readText)).rejects.toThrow("missing environment");
+    await expect(resolveEnvironment("bad", readText)).rejects.toThrow("Invalid environment name");
+  });
+});
+
+describe("parseDiscoveryConfig", () => {
+  test("keeps a valid default environment", () => {
+    expect(parseDiscoveryConfig('{"defaultEnvironment":"staging"}')?.defaultEnvironment).toBe(
+      "staging",
+    );
+  });
+
+  test("drops an invalid default environment instead of rejecting the config", () => {
+    const config = parseDiscoveryConfig('{"defaultEnvironment":"../prod","include":["*.http"]}');
+
+    expect(config?.defaultEnvironment).toBeUndefined();
+    expect(config?.include).toEqual(["*.http"]);
+  });
+});